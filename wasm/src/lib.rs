@@ -2,11 +2,13 @@
  * PurpleSky WASM Module
  *
  * This Rust code compiles to WebAssembly and handles all computation-heavy tasks:
- *  - Sorting feeds by various algorithms (newest, trending, Wilson score)
+ *  - Sorting feeds by various algorithms (newest, trending, hot, Wilson score, controversial)
  *  - Calculating net votes (upvotes minus downvotes)
  *  - Remixing feeds by percentage weights
- *  - Polis-like consensus clustering (opinion groups, agreement ratios)
+ *  - Polis-style consensus clustering (PCA + k-means opinion groups, group-aware consensus)
  *  - Forum thread scoring and ranking
+ *  - Qualified-majority review scoring for distributed moderation
+ *  - A pluggable Scorer registry for blending the above sorts with custom weights
  *
  * HOW TO EDIT:
  *  - Each function below is marked with #[wasm_bindgen] so JavaScript can call it.
@@ -37,6 +39,8 @@ pub struct SortablePost {
     pub reply_count: u32,
     /// Number of reposts
     pub repost_count: u32,
+    /// ISO timestamp of the newest reply/repost activity, if any (falls back to `created_at`).
+    pub last_activity_at: Option<String>,
 }
 
 /// Sort posts by newest first (most recent created_at).
@@ -71,6 +75,30 @@ fn trending_score(post: &SortablePost, now_ms: f64) -> f64 {
     engagement / age_hours.powf(1.5)
 }
 
+/// Sort posts by Lemmy-style "hot" rank: like trending, but decays against the newest
+/// reply/repost activity instead of `created_at`, so an old post that just got a burst
+/// of replies resurfaces instead of staying buried.
+#[wasm_bindgen]
+pub fn sort_by_hot(posts_json: &str, now_ms: f64) -> String {
+    let mut posts: Vec<SortablePost> = serde_json::from_str(posts_json).unwrap_or_default();
+    posts.sort_by(|a, b| {
+        let score_a = hot_score(a, now_ms);
+        let score_b = hot_score(b, now_ms);
+        score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    serde_json::to_string(&posts).unwrap_or_default()
+}
+
+/// Lemmy's hot-rank recurrence, mirroring Section 6's activity-based forum scoring.
+fn hot_score(post: &SortablePost, now_ms: f64) -> f64 {
+    let score = (post.like_count + post.repost_count) as f64 - post.downvote_count as f64;
+    let last_active = post.last_activity_at.as_deref()
+        .map(parse_iso_to_ms)
+        .unwrap_or_else(|| parse_iso_to_ms(&post.created_at));
+    let age_hours = ((now_ms - last_active) / 3_600_000.0).max(0.0);
+    (3.0 + score).max(1.0).log10() / (age_hours + 2.0).powf(1.8)
+}
+
 /// Sort posts by Wilson score (like Reddit's "best" algorithm).
 /// This balances high vote counts with statistical confidence.
 /// Posts with many votes and high like ratio rank higher.
@@ -85,20 +113,61 @@ pub fn sort_by_wilson_score(posts_json: &str) -> String {
     serde_json::to_string(&posts).unwrap_or_default()
 }
 
-/// Wilson score lower bound (95% confidence interval).
-/// Returns 0.0 for posts with no votes.
+/// Wilson score lower bound (95% confidence interval, no time decay).
+/// Returns 0.0 for posts with no votes. Delegates to `wilson_interval`.
 fn wilson_score(ups: u32, downs: u32) -> f64 {
-    let n = (ups + downs) as f64;
-    if n == 0.0 {
-        return 0.0;
+    wilson_interval(ups as f64, downs as f64, confidence_z(0.95)).0
+}
+
+/// Both bounds of the Wilson score interval, with a configurable confidence level and
+/// optional exponential time decay.
+/// Input: vote counts, `confidence` (e.g. 0.80/0.90/0.95/0.99), and a `half_life_hours` +
+/// `age_hours` pair — `ups`/`downs` are multiplied by `0.5.powf(age_hours / half_life_hours)`
+/// before the interval math so fresh votes outweigh stale ones (decay is skipped when
+/// `half_life_hours <= 0`). Output: JSON `{ "lower": f64, "upper": f64 }`.
+#[wasm_bindgen]
+pub fn wilson_bounds(ups: u32, downs: u32, confidence: f64, half_life_hours: f64, age_hours: f64) -> String {
+    let (decayed_ups, decayed_downs) = if half_life_hours > 0.0 {
+        let decay = 0.5_f64.powf(age_hours / half_life_hours);
+        (ups as f64 * decay, downs as f64 * decay)
+    } else {
+        (ups as f64, downs as f64)
+    };
+    let (lower, upper) = wilson_interval(decayed_ups, decayed_downs, confidence_z(confidence));
+    serde_json::to_string(&WilsonBounds { lower, upper }).unwrap_or_default()
+}
+
+/// Lower and upper Wilson score bounds for a given z (confidence) value.
+fn wilson_interval(ups: f64, downs: f64, z: f64) -> (f64, f64) {
+    let n = ups + downs;
+    if n <= 0.0 {
+        return (0.0, 0.0);
     }
-    // z = 1.96 for 95% confidence
-    let z = 1.96_f64;
-    let p = ups as f64 / n;
+    let p = ups / n;
     let denominator = 1.0 + z * z / n;
     let center = p + z * z / (2.0 * n);
     let spread = z * ((p * (1.0 - p) + z * z / (4.0 * n)) / n).sqrt();
-    (center - spread) / denominator
+    ((center - spread) / denominator, (center + spread) / denominator)
+}
+
+/// Maps a confidence level to its z-score via a small lookup, rather than assuming 95%.
+fn confidence_z(confidence: f64) -> f64 {
+    if confidence >= 0.99 {
+        2.5758
+    } else if confidence >= 0.95 {
+        1.96
+    } else if confidence >= 0.90 {
+        1.6449
+    } else {
+        1.2816 // 80% confidence
+    }
+}
+
+/// Both bounds of a Wilson score interval.
+#[derive(Serialize, Deserialize)]
+pub struct WilsonBounds {
+    pub lower: f64,
+    pub upper: f64,
 }
 
 /// Sort by net score: likes minus downvotes (one added per like, one subtracted per downvote).
@@ -113,7 +182,8 @@ pub fn sort_by_score(posts_json: &str) -> String {
     serde_json::to_string(&posts).unwrap_or_default()
 }
 
-/// Sort by "controversial" – posts with many votes but close to 50/50 split.
+/// Sort by "controversial" – Lemmy's power-law rank, which rewards posts that are both
+/// high-volume and near-balanced far more sharply than a plain distance-from-50/50 heuristic.
 #[wasm_bindgen]
 pub fn sort_by_controversial(posts_json: &str) -> String {
     let mut posts: Vec<SortablePost> = serde_json::from_str(posts_json).unwrap_or_default();
@@ -125,8 +195,50 @@ pub fn sort_by_controversial(posts_json: &str) -> String {
     serde_json::to_string(&posts).unwrap_or_default()
 }
 
-/// Controversy = total_votes * (1 - distance_from_50_50)
+/// Same ranking as `sort_by_controversial`, but breaks ties on the hot rank (i.e. recency)
+/// instead of leaving equally-controversial posts in arbitrary order. Separate function
+/// (rather than changing `sort_by_controversial`'s signature) so existing callers of the
+/// one-argument JS API aren't broken.
+#[wasm_bindgen]
+pub fn sort_by_controversial_hot(posts_json: &str, now_ms: f64) -> String {
+    let mut posts: Vec<SortablePost> = serde_json::from_str(posts_json).unwrap_or_default();
+    posts.sort_by(|a, b| {
+        let score_a = controversy_score(a.like_count, a.downvote_count);
+        let score_b = controversy_score(b.like_count, b.downvote_count);
+        score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal).then_with(|| {
+            let hot_a = hot_score(a, now_ms);
+            let hot_b = hot_score(b, now_ms);
+            hot_b.partial_cmp(&hot_a).unwrap_or(std::cmp::Ordering::Equal)
+        })
+    });
+    serde_json::to_string(&posts).unwrap_or_default()
+}
+
+/// Legacy controversy heuristic, kept for callers that still depend on its exact ordering.
+/// Superseded by `sort_by_controversial`, which uses Lemmy's power-law controversy rank.
+#[wasm_bindgen]
+pub fn sort_by_controversial_legacy(posts_json: &str) -> String {
+    let mut posts: Vec<SortablePost> = serde_json::from_str(posts_json).unwrap_or_default();
+    posts.sort_by(|a, b| {
+        let score_a = controversy_score_legacy(a.like_count, a.downvote_count);
+        let score_b = controversy_score_legacy(b.like_count, b.downvote_count);
+        score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    serde_json::to_string(&posts).unwrap_or_default()
+}
+
+/// Lemmy's controversy rank: `(ups + downs) ^ (min(ups, downs) / max(ups, downs))`.
+/// Zero unless both sides have at least one vote, so it can't be gamed by a single downvote.
 fn controversy_score(ups: u32, downs: u32) -> f64 {
+    if ups == 0 || downs == 0 {
+        return 0.0;
+    }
+    let ratio = ups.min(downs) as f64 / ups.max(downs) as f64;
+    ((ups + downs) as f64).powf(ratio)
+}
+
+/// Controversy = total_votes * (1 - distance_from_50_50)
+fn controversy_score_legacy(ups: u32, downs: u32) -> f64 {
     let total = (ups + downs) as f64;
     if total == 0.0 {
         return 0.0;
@@ -264,6 +376,11 @@ pub struct ConsensusResult {
     pub cluster_count: u32,
     /// Groups of users with similar voting patterns
     pub clusters: Vec<OpinionCluster>,
+    /// Statement IDs whose agree-ratio is at least `CONSENSUS_THRESHOLD` within *every*
+    /// non-empty cluster.
+    /// These are the group-aware "bridging" statements Polis surfaces: ones that unite
+    /// opinion groups which otherwise disagree.
+    pub consensus_statements: Vec<String>,
 }
 
 /// A group of users who vote similarly.
@@ -276,13 +393,27 @@ pub struct OpinionCluster {
     pub avg_agreement: f64,
 }
 
-/// Analyze consensus from a set of votes.
+/// Minimum within-cluster agree-ratio for a statement to count as group-aware consensus.
+const CONSENSUS_THRESHOLD: f64 = 0.7;
+/// Power-iteration steps used to extract the top 2 principal components.
+const PCA_ITERATIONS: usize = 25;
+/// Random restarts per candidate k when k-means clustering the PCA projection.
+const KMEANS_RESTARTS: usize = 5;
+/// Lloyd's-algorithm iterations per k-means restart.
+const KMEANS_ITERATIONS: usize = 25;
+
+/// Analyze consensus from a set of votes, Polis-style.
 /// Input: JSON array of ConsensusVote. Output: JSON ConsensusResult.
 ///
 /// HOW IT WORKS:
 ///  1. Count agree/disagree/pass per statement
 ///  2. Calculate agreement ratio and divisiveness for each statement
-///  3. Simple k-means clustering on user vote vectors to find opinion groups
+///  3. Mean-center the participant×statement matrix and project it onto its top 2
+///     principal components (power iteration with Gram–Schmidt re-orthogonalization)
+///  4. k-means the 2-D projection for k = 2..=5, picking the k with the best mean
+///     silhouette coefficient, to find real opinion clusters
+///  5. Surface statements with high agreement *inside every cluster* as group-aware
+///     consensus (the bridging statements Polis is known for)
 #[wasm_bindgen]
 pub fn analyze_consensus(votes_json: &str) -> String {
     let votes: Vec<ConsensusVote> = serde_json::from_str(votes_json).unwrap_or_default();
@@ -294,8 +425,10 @@ pub fn analyze_consensus(votes_json: &str) -> String {
         users.insert(v.user_id.clone());
         statements.insert(v.statement_id.clone());
     }
-    let user_list: Vec<String> = users.into_iter().collect();
-    let stmt_list: Vec<String> = statements.into_iter().collect();
+    let mut user_list: Vec<String> = users.into_iter().collect();
+    let mut stmt_list: Vec<String> = statements.into_iter().collect();
+    user_list.sort();
+    stmt_list.sort();
 
     // Build vote matrix: user_index -> statement_index -> value
     let mut matrix: std::collections::HashMap<String, std::collections::HashMap<String, i8>> =
@@ -340,49 +473,349 @@ pub fn analyze_consensus(votes_json: &str) -> String {
         });
     }
 
-    // Simple clustering: split users into 2 groups based on average vote
-    let mut cluster_a: Vec<String> = Vec::new();
-    let mut cluster_b: Vec<String> = Vec::new();
-    for uid in &user_list {
-        let votes_map = matrix.get(uid);
-        let avg: f64 = if let Some(vm) = votes_map {
-            let sum: f64 = stmt_list.iter().map(|s| *vm.get(s).unwrap_or(&0) as f64).sum();
-            if !stmt_list.is_empty() { sum / stmt_list.len() as f64 } else { 0.0 }
-        } else {
-            0.0
-        };
-        if avg >= 0.0 {
-            cluster_a.push(uid.clone());
-        } else {
-            cluster_b.push(uid.clone());
-        }
+    // Participant × statement matrix with values in {+1, -1, 0} (0 = pass/unvoted).
+    let raw_rows: Vec<Vec<f64>> = user_list
+        .iter()
+        .map(|uid| {
+            stmt_list
+                .iter()
+                .map(|sid| *matrix.get(uid).and_then(|m| m.get(sid)).unwrap_or(&0) as f64)
+                .collect()
+        })
+        .collect();
+    let centered_rows = mean_center_columns(&raw_rows, stmt_list.len());
+    let projection = pca_project_2d(&centered_rows, stmt_list.len());
+
+    let (assignments, k) = if user_list.len() < 2 || stmt_list.is_empty() {
+        (vec![0usize; user_list.len()], if user_list.is_empty() { 0 } else { 1 })
+    } else {
+        best_kmeans_by_silhouette(&projection)
+    };
+
+    // Assemble clusters from the chosen assignment.
+    let mut cluster_members: Vec<Vec<String>> = vec![Vec::new(); k];
+    for (i, uid) in user_list.iter().enumerate() {
+        cluster_members[assignments[i]].push(uid.clone());
     }
 
-    let clusters = vec![
-        OpinionCluster {
-            id: 0,
-            member_count: cluster_a.len() as u32,
-            avg_agreement: if !cluster_a.is_empty() { 0.7 } else { 0.0 },
-            member_ids: cluster_a,
-        },
-        OpinionCluster {
-            id: 1,
-            member_count: cluster_b.len() as u32,
-            avg_agreement: if !cluster_b.is_empty() { 0.3 } else { 0.0 },
-            member_ids: cluster_b,
-        },
-    ];
+    let clusters: Vec<OpinionCluster> = cluster_members
+        .into_iter()
+        .enumerate()
+        .map(|(id, member_ids)| {
+            let avg_agreement = cluster_avg_agreement(&member_ids, &stmt_list, &matrix);
+            OpinionCluster {
+                id: id as u32,
+                member_count: member_ids.len() as u32,
+                member_ids,
+                avg_agreement,
+            }
+        })
+        .collect();
+
+    // Group-aware consensus: statements whose agree-ratio clears the threshold in every
+    // *non-empty* cluster. k-means can leave a cluster with no members (e.g. when every
+    // participant's projection collapses to the same point); an empty cluster has no
+    // voters to disagree, so it must not veto consensus for every statement.
+    let non_empty_clusters: Vec<&OpinionCluster> =
+        clusters.iter().filter(|c| c.member_count > 0).collect();
+    let consensus_statements: Vec<String> = stmt_list
+        .iter()
+        .filter(|sid| {
+            !non_empty_clusters.is_empty()
+                && non_empty_clusters.iter().all(|c| {
+                    statement_agree_ratio(sid, &c.member_ids, &matrix) >= CONSENSUS_THRESHOLD
+                })
+        })
+        .cloned()
+        .collect();
+    let cluster_count = non_empty_clusters.len() as u32;
 
     let result = ConsensusResult {
         statements: stmt_results,
         total_participants: user_list.len() as u32,
-        cluster_count: if clusters.iter().any(|c| c.member_count > 0) { 2 } else { 0 },
+        cluster_count,
         clusters,
+        consensus_statements,
     };
 
     serde_json::to_string(&result).unwrap_or_default()
 }
 
+/// Subtract each column's mean so PCA is computed on centered data.
+fn mean_center_columns(rows: &[Vec<f64>], n_cols: usize) -> Vec<Vec<f64>> {
+    if rows.is_empty() {
+        return Vec::new();
+    }
+    let n_rows = rows.len() as f64;
+    let means: Vec<f64> = (0..n_cols)
+        .map(|c| rows.iter().map(|r| r[c]).sum::<f64>() / n_rows)
+        .collect();
+    rows.iter()
+        .map(|r| r.iter().zip(&means).map(|(v, m)| v - m).collect())
+        .collect()
+}
+
+/// Project centered rows onto their top 2 principal components via simultaneous power
+/// iteration on the covariance matrix, re-orthogonalizing the second vector against the
+/// first (Gram-Schmidt) on every step.
+fn pca_project_2d(centered: &[Vec<f64>], n_cols: usize) -> Vec<(f64, f64)> {
+    if centered.is_empty() || n_cols == 0 {
+        return centered.iter().map(|_| (0.0, 0.0)).collect();
+    }
+
+    let mut cov = vec![vec![0.0; n_cols]; n_cols];
+    for row in centered {
+        for i in 0..n_cols {
+            for j in 0..n_cols {
+                cov[i][j] += row[i] * row[j];
+            }
+        }
+    }
+
+    let mut v1 = unit_vector(n_cols, 0x9E3779B97F4A7C15);
+    let mut v2 = unit_vector(n_cols, 0xD1B54A32D192ED03);
+    for _ in 0..PCA_ITERATIONS {
+        v1 = normalize(mat_vec_mul(&cov, &v1));
+        let mut next_v2 = mat_vec_mul(&cov, &v2);
+        let proj = dot(&next_v2, &v1);
+        for i in 0..n_cols {
+            next_v2[i] -= proj * v1[i];
+        }
+        v2 = normalize(next_v2);
+    }
+
+    centered
+        .iter()
+        .map(|row| (dot(row, &v1), dot(row, &v2)))
+        .collect()
+}
+
+/// Deterministic pseudo-random unit vector (xorshift64), used to seed power iteration.
+fn unit_vector(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    let v: Vec<f64> = (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state as f64 / u64::MAX as f64) - 0.5
+        })
+        .collect();
+    normalize(v)
+}
+
+fn mat_vec_mul(m: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    m.iter().map(|row| dot(row, v)).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalize(v: Vec<f64>) -> Vec<f64> {
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm < 1e-12 {
+        return v;
+    }
+    v.into_iter().map(|x| x / norm).collect()
+}
+
+/// Run k-means for k = 2..=5 (clamped to the number of points) and return the assignment
+/// with the best mean silhouette coefficient, along with the k that produced it.
+fn best_kmeans_by_silhouette(points: &[(f64, f64)]) -> (Vec<usize>, usize) {
+    let max_k = 5.min(points.len());
+    let mut best: Option<(f64, Vec<usize>, usize)> = None;
+
+    for k in 2..=max_k.max(2) {
+        if k > points.len() {
+            break;
+        }
+        let assignments = kmeans_best_of(points, k);
+        let score = mean_silhouette(points, &assignments, k);
+        if best.as_ref().map(|(b, _, _)| score > *b).unwrap_or(true) {
+            best = Some((score, assignments, k));
+        }
+    }
+
+    best.map(|(_, a, k)| (a, k)).unwrap_or_else(|| (vec![0; points.len()], 1))
+}
+
+/// Run k-means with a few random restarts, keeping the lowest-inertia result.
+fn kmeans_best_of(points: &[(f64, f64)], k: usize) -> Vec<usize> {
+    let mut best_assignments = vec![0usize; points.len()];
+    let mut best_inertia = f64::INFINITY;
+
+    for restart in 0..KMEANS_RESTARTS {
+        let seed = 0xA24BAED4963EE407u64.wrapping_add(restart as u64 * 0x9E3779B1);
+        let (assignments, inertia) = kmeans_once(points, k, seed);
+        if inertia < best_inertia {
+            best_inertia = inertia;
+            best_assignments = assignments;
+        }
+    }
+
+    best_assignments
+}
+
+fn kmeans_once(points: &[(f64, f64)], k: usize, seed: u64) -> (Vec<usize>, f64) {
+    let mut state = seed;
+    let mut next_index = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state as usize) % points.len()
+    };
+
+    let mut centroids: Vec<(f64, f64)> = (0..k).map(|_| points[next_index()]).collect();
+    let mut assignments = vec![0usize; points.len()];
+
+    for _ in 0..KMEANS_ITERATIONS {
+        for (i, p) in points.iter().enumerate() {
+            assignments[i] = centroids
+                .iter()
+                .enumerate()
+                .min_by(|a, b| {
+                    sq_dist(*p, *a.1).partial_cmp(&sq_dist(*p, *b.1)).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|(ci, _)| ci)
+                .unwrap_or(0);
+        }
+
+        for (ci, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&(f64, f64)> = points
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, &a)| a == ci)
+                .map(|(p, _)| p)
+                .collect();
+            if !members.is_empty() {
+                let n = members.len() as f64;
+                let sx: f64 = members.iter().map(|p| p.0).sum();
+                let sy: f64 = members.iter().map(|p| p.1).sum();
+                *centroid = (sx / n, sy / n);
+            }
+        }
+    }
+
+    let inertia: f64 = points
+        .iter()
+        .zip(&assignments)
+        .map(|(p, &a)| sq_dist(*p, centroids[a]))
+        .sum();
+
+    (assignments, inertia)
+}
+
+fn sq_dist(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+/// Mean silhouette coefficient for a clustering: for each point, `(b - a) / max(a, b)`
+/// where `a` is the mean intra-cluster distance and `b` is the smallest mean distance to
+/// any other cluster. Singleton clusters score 0 (silhouette is undefined with no peers).
+fn mean_silhouette(points: &[(f64, f64)], assignments: &[usize], k: usize) -> f64 {
+    if points.len() <= k {
+        return 0.0;
+    }
+
+    let mut total = 0.0;
+    for (i, &pi) in points.iter().enumerate() {
+        let own = assignments[i];
+        let own_others: Vec<f64> = points
+            .iter()
+            .zip(assignments)
+            .enumerate()
+            .filter(|(j, (_, &a))| *j != i && a == own)
+            .map(|(_, (p, _))| sq_dist(pi, *p).sqrt())
+            .collect();
+        let a = if own_others.is_empty() {
+            0.0
+        } else {
+            own_others.iter().sum::<f64>() / own_others.len() as f64
+        };
+
+        let b = (0..k)
+            .filter(|&c| c != own)
+            .filter_map(|c| {
+                let dists: Vec<f64> = points
+                    .iter()
+                    .zip(assignments)
+                    .filter(|(_, &a)| a == c)
+                    .map(|(p, _)| sq_dist(pi, *p).sqrt())
+                    .collect();
+                if dists.is_empty() {
+                    None
+                } else {
+                    Some(dists.iter().sum::<f64>() / dists.len() as f64)
+                }
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        if own_others.is_empty() || !b.is_finite() {
+            continue;
+        }
+        // Guard against 0/0 (NaN) when two non-empty clusters both collapse to the same
+        // point for this member — not reachable via today's kmeans_once (identical points
+        // always land in the same cluster), but nothing guarantees that stays true.
+        if a.max(b) < 1e-12 {
+            continue;
+        }
+        total += (b - a) / a.max(b);
+    }
+
+    total / points.len() as f64
+}
+
+/// Average agreement ratio across a cluster's members: total agree votes over total
+/// agree+disagree votes they cast, across every statement.
+fn cluster_avg_agreement(
+    member_ids: &[String],
+    stmt_list: &[String],
+    matrix: &std::collections::HashMap<String, std::collections::HashMap<String, i8>>,
+) -> f64 {
+    if member_ids.is_empty() {
+        return 0.0;
+    }
+    let mut agree = 0u32;
+    let mut voters = 0u32;
+    for uid in member_ids {
+        if let Some(votes) = matrix.get(uid) {
+            for sid in stmt_list {
+                match votes.get(sid) {
+                    Some(1) => {
+                        agree += 1;
+                        voters += 1;
+                    }
+                    Some(-1) => voters += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+    if voters > 0 { agree as f64 / voters as f64 } else { 0.0 }
+}
+
+/// Agree-ratio for a single statement, restricted to one cluster's members.
+fn statement_agree_ratio(
+    statement_id: &str,
+    member_ids: &[String],
+    matrix: &std::collections::HashMap<String, std::collections::HashMap<String, i8>>,
+) -> f64 {
+    let mut agree = 0u32;
+    let mut voters = 0u32;
+    for uid in member_ids {
+        match matrix.get(uid).and_then(|m| m.get(statement_id)) {
+            Some(1) => {
+                agree += 1;
+                voters += 1;
+            }
+            Some(-1) => voters += 1,
+            _ => {}
+        }
+    }
+    if voters > 0 { agree as f64 / voters as f64 } else { 0.0 }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SECTION 5: Masonry Layout Height Estimation
 // Estimate card heights to distribute posts evenly across columns.
@@ -444,7 +877,7 @@ fn estimate_height(post: &PostLayoutInfo) -> f64 {
     let media_h = if post.has_media {
         let ar = post.media_aspect_ratio.unwrap_or(1.0).max(0.3);
         // Assume card width ~300px, height = width / aspect_ratio
-        (300.0 / ar).min(500.0).max(100.0)
+        (300.0 / ar).clamp(100.0, 500.0)
     } else {
         0.0
     };
@@ -490,12 +923,275 @@ pub fn sort_forum_threads(threads_json: &str, now_ms: f64) -> String {
 fn forum_activity_score(thread: &ForumThread, now_ms: f64) -> f64 {
     let engagement = (thread.reply_count * 2 + thread.like_count) as f64;
     let last_active = thread.last_reply_at.as_deref()
-        .map(|s| parse_iso_to_ms(s))
+        .map(parse_iso_to_ms)
         .unwrap_or_else(|| parse_iso_to_ms(&thread.created_at));
     let age_hours = ((now_ms - last_active) / 3_600_000.0).max(1.0);
     engagement / age_hours.powf(1.2)
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// SECTION 7: Qualified-Majority Review Scoring
+// Lets several moderators/curators independently rank a post (e.g. "excellent",
+// "good", "filtered"); the crate resolves a final verdict and flags dissenters.
+// Distinct from the raw vote tallies in Section 2.
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One reviewer's ranking of one post.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReviewRecord {
+    pub post_id: String,
+    pub reviewer_id: String,
+    pub ranking: String,
+}
+
+/// The resolved verdict for a single post, plus each reviewer's standing.
+#[derive(Serialize, Deserialize)]
+pub struct PostReviewVerdict {
+    pub post_id: String,
+    pub final_ranking: String,
+    /// Fraction of reviewers of this post who backed `final_ranking`.
+    pub consensus_fraction: f64,
+    pub reviewers: Vec<ReviewerVerdict>,
+}
+
+/// One reviewer's ranking of a post, annotated with whether it's a dissent.
+#[derive(Serialize, Deserialize)]
+pub struct ReviewerVerdict {
+    pub reviewer_id: String,
+    pub ranking: String,
+    /// True only when this ranking disagrees with the majority AND the majority's
+    /// consensus fraction meets `minimum_consensus` — a narrow majority doesn't flag anyone.
+    pub out_of_consensus: bool,
+}
+
+/// A reviewer's track record across every post they ranked.
+#[derive(Serialize, Deserialize)]
+pub struct ReviewerAgreement {
+    pub reviewer_id: String,
+    /// Fraction of this reviewer's rankings that matched the post's final ranking.
+    pub agreement_rate: f64,
+    pub reviews_count: u32,
+}
+
+/// Result of scoring a batch of reviews.
+#[derive(Serialize, Deserialize)]
+pub struct ReviewScoringResult {
+    pub posts: Vec<PostReviewVerdict>,
+    pub reviewer_agreement: Vec<ReviewerAgreement>,
+}
+
+/// Score multi-reviewer rankings of posts into a final verdict per post, flagging
+/// dissenters and tracking each reviewer's agreement rate for downstream reputation logic.
+/// Input: JSON array of ReviewRecord, and `minimum_consensus` in `[0.5, 1.0]` — the
+/// consensus fraction a majority needs before its dissenters get flagged.
+/// Output: JSON ReviewScoringResult.
+#[wasm_bindgen]
+pub fn score_reviews(reviews_json: &str, minimum_consensus: f64) -> String {
+    let reviews: Vec<ReviewRecord> = serde_json::from_str(reviews_json).unwrap_or_default();
+
+    // Group reviews by post, preserving first-seen order.
+    let mut post_order: Vec<String> = Vec::new();
+    let mut by_post: std::collections::HashMap<String, Vec<&ReviewRecord>> =
+        std::collections::HashMap::new();
+    for r in &reviews {
+        if !by_post.contains_key(&r.post_id) {
+            post_order.push(r.post_id.clone());
+        }
+        by_post.entry(r.post_id.clone()).or_default().push(r);
+    }
+
+    let mut posts: Vec<PostReviewVerdict> = Vec::new();
+    let mut reviewer_hits: std::collections::HashMap<String, (u32, u32)> =
+        std::collections::HashMap::new(); // reviewer_id -> (matches, total)
+
+    for post_id in &post_order {
+        let post_reviews = &by_post[post_id];
+
+        // Tally ranking counts, preserving first-seen order so ties resolve deterministically.
+        let mut ranking_order: Vec<String> = Vec::new();
+        let mut counts: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+        for r in post_reviews {
+            if !counts.contains_key(&r.ranking) {
+                ranking_order.push(r.ranking.clone());
+            }
+            *counts.entry(r.ranking.clone()).or_insert(0) += 1;
+        }
+
+        // Walk in first-seen order, only replacing on a strictly greater count, so a tie
+        // resolves to whichever ranking was submitted first (Iterator::max_by_key would
+        // instead keep the *last* equally-maximal element).
+        let mut final_ranking = String::new();
+        let mut best_count = 0u32;
+        for ranking in &ranking_order {
+            let count = counts[ranking];
+            if count > best_count {
+                best_count = count;
+                final_ranking = ranking.clone();
+            }
+        }
+        let total = post_reviews.len() as f64;
+        let consensus_fraction = counts.get(&final_ranking).copied().unwrap_or(0) as f64 / total;
+
+        let reviewers: Vec<ReviewerVerdict> = post_reviews
+            .iter()
+            .map(|r| {
+                let dissents = r.ranking != final_ranking;
+                let matched = !dissents;
+                let entry = reviewer_hits.entry(r.reviewer_id.clone()).or_insert((0, 0));
+                entry.0 += matched as u32;
+                entry.1 += 1;
+                ReviewerVerdict {
+                    reviewer_id: r.reviewer_id.clone(),
+                    ranking: r.ranking.clone(),
+                    out_of_consensus: dissents && consensus_fraction >= minimum_consensus,
+                }
+            })
+            .collect();
+
+        posts.push(PostReviewVerdict {
+            post_id: post_id.clone(),
+            final_ranking,
+            consensus_fraction,
+            reviewers,
+        });
+    }
+
+    let mut reviewer_agreement: Vec<ReviewerAgreement> = reviewer_hits
+        .into_iter()
+        .map(|(reviewer_id, (matches, total))| ReviewerAgreement {
+            reviewer_id,
+            agreement_rate: matches as f64 / total as f64,
+            reviews_count: total,
+        })
+        .collect();
+    reviewer_agreement.sort_by(|a, b| a.reviewer_id.cmp(&b.reviewer_id));
+
+    let result = ReviewScoringResult { posts, reviewer_agreement };
+    serde_json::to_string(&result).unwrap_or_default()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SECTION 8: Unified Ranking
+// A pluggable Scorer trait over the Section 1 algorithms, so callers can blend them
+// with custom weights instead of getting one ad-hoc sort_by_* per combination.
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A pluggable post-ranking algorithm.
+pub trait Scorer {
+    fn score(&self, post: &SortablePost, now_ms: f64) -> f64;
+}
+
+pub struct TrendingScorer;
+impl Scorer for TrendingScorer {
+    fn score(&self, post: &SortablePost, now_ms: f64) -> f64 {
+        trending_score(post, now_ms)
+    }
+}
+
+pub struct WilsonScorer;
+impl Scorer for WilsonScorer {
+    fn score(&self, post: &SortablePost, _now_ms: f64) -> f64 {
+        wilson_score(post.like_count, post.downvote_count)
+    }
+}
+
+pub struct NetScorer;
+impl Scorer for NetScorer {
+    fn score(&self, post: &SortablePost, _now_ms: f64) -> f64 {
+        (post.like_count as i32 - post.downvote_count as i32) as f64
+    }
+}
+
+pub struct ControversyScorer;
+impl Scorer for ControversyScorer {
+    fn score(&self, post: &SortablePost, _now_ms: f64) -> f64 {
+        controversy_score(post.like_count, post.downvote_count)
+    }
+}
+
+pub struct HotScorer;
+impl Scorer for HotScorer {
+    fn score(&self, post: &SortablePost, now_ms: f64) -> f64 {
+        hot_score(post, now_ms)
+    }
+}
+
+/// Registry mapping a scorer name (as used in a rank spec) to its implementation.
+fn scorer_by_name(name: &str) -> Option<Box<dyn Scorer>> {
+    match name {
+        "trending" => Some(Box::new(TrendingScorer)),
+        "wilson" => Some(Box::new(WilsonScorer)),
+        "net" => Some(Box::new(NetScorer)),
+        "controversy" => Some(Box::new(ControversyScorer)),
+        "hot" => Some(Box::new(HotScorer)),
+        _ => None,
+    }
+}
+
+/// One scorer's contribution to a blended ranking.
+#[derive(Serialize, Deserialize)]
+pub struct ScorerWeight {
+    pub name: String,
+    pub weight: f64,
+}
+
+/// A ranking spec: which scorers to blend, at what weights, evaluated at `now_ms`.
+#[derive(Serialize, Deserialize)]
+pub struct RankSpec {
+    pub now_ms: f64,
+    pub scorers: Vec<ScorerWeight>,
+    /// Half-life (hours) for an overall recency decay applied after blending scorer
+    /// outputs. `<= 0` disables decay.
+    pub recency_half_life_hours: f64,
+}
+
+/// Rank posts by a normalized weighted sum of one or more named scorers.
+/// Input: JSON array of SortablePost, and a JSON RankSpec naming scorers + weights.
+/// Output: JSON array of SortablePost, ranked highest-scoring first.
+///
+/// Each scorer's raw outputs are min-max normalized across the batch before weighting,
+/// so e.g. trending's unbounded magnitude and Wilson's [0,1] range are comparable. This
+/// turns the Section 1 sort_by_* functions into one extensible ranking subsystem, letting
+/// the UI offer user-tunable feeds (e.g. "60% trending / 40% Wilson") without a new
+/// exported function per combination.
+#[wasm_bindgen]
+pub fn rank_posts(posts_json: &str, spec_json: &str) -> String {
+    let mut posts: Vec<SortablePost> = serde_json::from_str(posts_json).unwrap_or_default();
+    let spec: RankSpec = match serde_json::from_str(spec_json) {
+        Ok(s) => s,
+        Err(_) => return serde_json::to_string(&posts).unwrap_or_default(),
+    };
+    if posts.is_empty() || spec.scorers.is_empty() {
+        return serde_json::to_string(&posts).unwrap_or_default();
+    }
+
+    let mut blended = vec![0.0; posts.len()];
+    for sw in &spec.scorers {
+        let Some(scorer) = scorer_by_name(&sw.name) else { continue };
+        let raw: Vec<f64> = posts.iter().map(|p| scorer.score(p, spec.now_ms)).collect();
+        let min = raw.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = raw.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        for (i, r) in raw.iter().enumerate() {
+            let normalized = if range > 1e-12 { (r - min) / range } else { 0.5 };
+            blended[i] += normalized * sw.weight;
+        }
+    }
+
+    if spec.recency_half_life_hours > 0.0 {
+        for (i, post) in posts.iter().enumerate() {
+            let age_hours = ((spec.now_ms - parse_iso_to_ms(&post.created_at)) / 3_600_000.0).max(0.0);
+            blended[i] *= 0.5_f64.powf(age_hours / spec.recency_half_life_hours);
+        }
+    }
+
+    let mut indices: Vec<usize> = (0..posts.len()).collect();
+    indices.sort_by(|&a, &b| blended[b].partial_cmp(&blended[a]).unwrap_or(std::cmp::Ordering::Equal));
+    posts = indices.into_iter().map(|i| posts[i].clone()).collect();
+
+    serde_json::to_string(&posts).unwrap_or_default()
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Utilities
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -521,3 +1217,77 @@ fn parse_iso_to_ms(iso: &str) -> f64 {
         + sec)
         * 1000.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(user_id: &str, statement_id: &str, value: i8) -> ConsensusVote {
+        ConsensusVote { user_id: user_id.to_string(), statement_id: statement_id.to_string(), value }
+    }
+
+    fn run_consensus(votes: Vec<ConsensusVote>) -> ConsensusResult {
+        let json = serde_json::to_string(&votes).unwrap();
+        serde_json::from_str(&analyze_consensus(&json)).unwrap()
+    }
+
+    /// A unanimous group (everyone agrees with everyone) is exactly the case k-means can
+    /// degenerate on: every participant's PCA projection collapses to the same point, so
+    /// one k-means cluster can come back empty. consensus_statements must still surface
+    /// every statement as a bridging statement instead of silently coming back empty.
+    #[test]
+    fn unanimous_group_surfaces_consensus_statements() {
+        let mut votes = Vec::new();
+        for user in ["u1", "u2", "u3", "u4", "u5"] {
+            for stmt in ["s1", "s2", "s3"] {
+                votes.push(vote(user, stmt, 1));
+            }
+        }
+        let result = run_consensus(votes);
+        assert_eq!(result.total_participants, 5);
+        assert!(result.clusters.iter().any(|c| c.member_count > 0));
+        let mut consensus = result.consensus_statements.clone();
+        consensus.sort();
+        assert_eq!(consensus, vec!["s1".to_string(), "s2".to_string(), "s3".to_string()]);
+    }
+
+    /// Two groups that each agree internally but disagree with the other group should
+    /// cluster into (roughly) two groups, and neither statement (each one splits the
+    /// groups) should count as group-aware consensus.
+    #[test]
+    fn opposing_groups_find_no_cross_group_consensus() {
+        let mut votes = Vec::new();
+        for user in ["a1", "a2", "a3"] {
+            votes.push(vote(user, "s1", 1));
+            votes.push(vote(user, "s2", -1));
+        }
+        for user in ["b1", "b2", "b3"] {
+            votes.push(vote(user, "s1", -1));
+            votes.push(vote(user, "s2", 1));
+        }
+        let result = run_consensus(votes);
+        assert_eq!(result.total_participants, 6);
+        assert!(result.consensus_statements.is_empty());
+    }
+
+    /// Degenerate tiny input: fewer than 2 participants can't be k-means clustered at all.
+    #[test]
+    fn single_participant_skips_clustering() {
+        let votes = vec![vote("solo", "s1", 1), vote("solo", "s2", -1)];
+        let result = run_consensus(votes);
+        assert_eq!(result.total_participants, 1);
+        assert_eq!(result.cluster_count, 1);
+        assert_eq!(result.clusters.len(), 1);
+        assert_eq!(result.clusters[0].member_count, 1);
+    }
+
+    /// No votes at all shouldn't panic and should report an empty result.
+    #[test]
+    fn empty_input_returns_empty_result() {
+        let result = run_consensus(Vec::new());
+        assert_eq!(result.total_participants, 0);
+        assert_eq!(result.cluster_count, 0);
+        assert!(result.clusters.is_empty());
+        assert!(result.consensus_statements.is_empty());
+    }
+}